@@ -18,13 +18,21 @@ use std::ffi::c_void;
 
 use once_cell::sync::Lazy;
 use windows_sys::Win32::{
-	Foundation::{BOOL, FARPROC, HWND},
-	Graphics::Dwm::{DwmExtendFrameIntoClientArea, DwmSetWindowAttribute, DWMWINDOWATTRIBUTE},
+	Foundation::{BOOL, FARPROC, HRESULT, HWND, LPARAM, LRESULT, WPARAM},
+	Graphics::{
+		Dwm::{DwmEnableBlurBehindWindow, DwmExtendFrameIntoClientArea, DwmSetWindowAttribute, DWMWINDOWATTRIBUTE, DWM_BLURBEHIND},
+		Gdi::{DeleteObject, HRGN}
+	},
 	System::{
 		LibraryLoader::{GetProcAddress, LoadLibraryA},
+		Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
 		SystemInformation::OSVERSIONINFOW
 	},
-	UI::Controls::MARGINS
+	UI::{
+		Accessibility::HIGHCONTRASTA,
+		Controls::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass, MARGINS},
+		WindowsAndMessaging::{SystemParametersInfoA, HCF_HIGHCONTRASTON, SPI_GETHIGHCONTRAST, WM_SETTINGCHANGE}
+	}
 };
 
 use crate::VibeError;
@@ -34,6 +42,36 @@ type WINDOWCOMPOSITIONATTRIB = u32;
 const DWMWA_USE_IMMERSIVE_DARK_MODE: DWMWINDOWATTRIBUTE = 20i32;
 const DWMWA_MICA_EFFECT: DWMWINDOWATTRIBUTE = 1029i32;
 const DWMWA_SYSTEMBACKDROP_TYPE: DWMWINDOWATTRIBUTE = 38i32;
+const DWMWA_WINDOW_CORNER_PREFERENCE: DWMWINDOWATTRIBUTE = 33i32;
+const DWMWA_BORDER_COLOR: DWMWINDOWATTRIBUTE = 34i32;
+const DWMWA_CAPTION_COLOR: DWMWINDOWATTRIBUTE = 35i32;
+const DWMWA_TEXT_COLOR: DWMWINDOWATTRIBUTE = 36i32;
+
+const DWMWA_COLOR_DEFAULT: u32 = 0xFFFFFFFF;
+const DWMWA_COLOR_NONE: u32 = 0xFFFFFFFE;
+
+const DWM_BB_ENABLE: u32 = 0x00000001;
+const DWM_BB_BLURREGION: u32 = 0x00000002;
+const DWM_BB_TRANSITIONONMAXIMIZED: u32 = 0x00000004;
+
+/// Mirrors `DWM_WINDOW_CORNER_PREFERENCE`.
+#[repr(i32)]
+pub enum DWM_WINDOW_CORNER_PREFERENCE {
+	DWMWCP_DEFAULT = 0,
+	DWMWCP_DONOTROUND = 1,
+	DWMWCP_ROUND = 2,
+	DWMWCP_ROUNDSMALL = 3
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+	Light,
+	Dark
+}
+
+/// Subclass ID used to identify vibe's `WM_SETTINGCHANGE` hook on a window, so it can be removed
+/// later without disturbing subclasses installed by other code.
+const THEME_WATCH_SUBCLASS_ID: usize = 0xACE5_BEEF;
 
 fn get_function_impl(library: &str, function: &str) -> Option<FARPROC> {
 	assert_eq!(library.chars().last(), Some('\0'));
@@ -93,7 +131,8 @@ struct WINDOWCOMPOSITIONATTRIBDATA {
 enum DWM_SYSTEMBACKDROP_TYPE {
 	DWMSBT_DISABLE = 1,
 	DWMSBT_MAINWINDOW = 2,      // Mica
-	DWMSBT_TRANSIENTWINDOW = 3  // Acrylic
+	DWMSBT_TRANSIENTWINDOW = 3, // Acrylic
+	DWMSBT_TABBEDWINDOW = 4     // Tabbed Mica
 }
 
 #[inline]
@@ -116,6 +155,29 @@ pub fn is_win11_22h2() -> bool {
 	WVER.2 >= 22621
 }
 
+/// Checks whether Desktop Window Manager composition is currently enabled. When it isn't (or DWM
+/// isn't running at all), blur/acrylic/mica calls have no effect, so callers should bail out with
+/// `VibeError::CompositionDisabled` instead of silently no-opping.
+pub fn is_composition_enabled() -> bool {
+	if let Some(DwmIsCompositionEnabled) = get_function!("dwmapi.dll", DwmIsCompositionEnabled, unsafe extern "system" fn(*mut BOOL) -> HRESULT) {
+		let mut enabled: BOOL = 0;
+		let hr = unsafe { DwmIsCompositionEnabled(&mut enabled) };
+		hr >= 0 && enabled != 0
+	} else {
+		false
+	}
+}
+
+/// Packs an RGBA colour into the `COLORREF` layout (`0x00BBGGRR`) expected by the `DWMWA_*_COLOR`
+/// attributes, or falls back to `none_sentinel` (one of `DWMWA_COLOR_DEFAULT`/`DWMWA_COLOR_NONE`)
+/// when no colour is given.
+fn pack_colorref(colour: Option<[u8; 4]>, none_sentinel: u32) -> u32 {
+	match colour {
+		Some([r, g, b, _]) => (r as u32) | (g as u32) << 8 | (b as u32) << 16,
+		None => none_sentinel
+	}
+}
+
 unsafe fn set_accent_policy(hwnd: HWND, accent_state: ACCENT_STATE, colour: Option<[u8; 4]>) {
 	if let Some(SetWindowCompositionAttribute) =
 		get_function!("user32.dll", SetWindowCompositionAttribute, unsafe extern "system" fn(HWND, *mut WINDOWCOMPOSITIONATTRIBDATA) -> BOOL)
@@ -193,7 +255,148 @@ pub fn force_light_theme(hwnd: HWND) -> Result<(), VibeError> {
 	Ok(())
 }
 
+/// Reads the OS-level light/dark preference, honouring a high-contrast override.
+pub fn detect_system_theme() -> Theme {
+	unsafe {
+		let mut high_contrast = HIGHCONTRASTA {
+			cbSize: std::mem::size_of::<HIGHCONTRASTA>() as u32,
+			dwFlags: 0,
+			lpszDefaultScheme: std::ptr::null_mut()
+		};
+		SystemParametersInfoA(SPI_GETHIGHCONTRAST, std::mem::size_of::<HIGHCONTRASTA>() as u32, &mut high_contrast as *mut _ as _, 0);
+		if high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0 {
+			// high-contrast schemes are always treated as light
+			return Theme::Light;
+		}
+	}
+
+	let subkey: Vec<u16> = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize\0".encode_utf16().collect();
+	let value_name: Vec<u16> = "AppsUseLightTheme\0".encode_utf16().collect();
+	let mut apps_use_light_theme: u32 = 1;
+	let mut size = std::mem::size_of::<u32>() as u32;
+	let status = unsafe {
+		RegGetValueW(
+			HKEY_CURRENT_USER,
+			subkey.as_ptr(),
+			value_name.as_ptr(),
+			RRF_RT_REG_DWORD,
+			std::ptr::null_mut(),
+			&mut apps_use_light_theme as *mut _ as _,
+			&mut size
+		)
+	};
+
+	if status == 0 && apps_use_light_theme == 0 { Theme::Dark } else { Theme::Light }
+}
+
+fn apply_theme(hwnd: HWND, theme: Theme) -> Result<(), VibeError> {
+	match theme {
+		Theme::Dark => force_dark_theme(hwnd),
+		Theme::Light => force_light_theme(hwnd)
+	}
+}
+
+/// Checks whether `lparam` of a `WM_SETTINGCHANGE` message points to the `"ImmersiveColorSet"`
+/// setting name.
+unsafe fn is_immersive_color_set(lparam: LPARAM) -> bool {
+	if lparam == 0 {
+		return false;
+	}
+	let setting = "ImmersiveColorSet\0".encode_utf16().collect::<Vec<_>>();
+	let ptr = lparam as *const u16;
+	for (i, expected) in setting.iter().enumerate() {
+		let actual = *ptr.add(i);
+		if actual != *expected {
+			return false;
+		}
+		if *expected == 0 {
+			break;
+		}
+	}
+	true
+}
+
+unsafe extern "system" fn theme_watch_subclass_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM, _id_subclass: usize, _ref_data: usize) -> LRESULT {
+	if msg == WM_SETTINGCHANGE && is_immersive_color_set(lparam) {
+		let _ = apply_theme(hwnd, detect_system_theme());
+	}
+	DefSubclassProc(hwnd, msg, wparam, lparam)
+}
+
+/// Installs a subclass hook on `hwnd` that reapplies the detected system theme whenever the user
+/// toggles light/dark mode at runtime, so `force_theme("auto")` stays live for the window.
+pub fn watch_system_theme(hwnd: HWND) -> Result<(), VibeError> {
+	if is_win10_1809() || is_win11() {
+		unsafe {
+			SetWindowSubclass(hwnd, Some(theme_watch_subclass_proc), THEME_WATCH_SUBCLASS_ID, 0);
+		}
+		Ok(())
+	} else {
+		Err(VibeError::UnsupportedPlatform("\"watch_system_theme()\" is only available on Windows 10 v1809+ or Windows 11"))
+	}
+}
+
+/// Removes a subclass hook previously installed by `watch_system_theme()`, so an explicit
+/// `force_dark_theme()`/`force_light_theme()` call isn't silently clobbered back to the
+/// OS-detected theme the next time the user toggles light/dark mode.
+pub fn unwatch_system_theme(hwnd: HWND) {
+	unsafe {
+		RemoveWindowSubclass(hwnd, Some(theme_watch_subclass_proc), THEME_WATCH_SUBCLASS_ID);
+	}
+}
+
+/// Blurs only `region` of the window's client area (or the whole client area when `region` is
+/// `None`), unlike the accent-policy-based `apply_acrylic()` which can only blur the entire
+/// window.
+pub fn apply_blur_region(hwnd: HWND, region: Option<HRGN>, transition_on_maximized: bool) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
+	let mut flags = DWM_BB_ENABLE | DWM_BB_BLURREGION;
+	if transition_on_maximized {
+		flags |= DWM_BB_TRANSITIONONMAXIMIZED;
+	}
+	let blur_behind = DWM_BLURBEHIND {
+		dwFlags: flags,
+		fEnable: 1,
+		hRgnBlur: region.unwrap_or(0),
+		fTransitionOnMaximized: transition_on_maximized as BOOL
+	};
+	unsafe {
+		DwmEnableBlurBehindWindow(hwnd, &blur_behind);
+		// DWM copies the region; per the DWM_BLURBEHIND contract the caller retains ownership of
+		// `hRgnBlur` and must free it after the call returns.
+		if let Some(region) = region {
+			DeleteObject(region);
+		}
+	}
+	Ok(())
+}
+
+/// Clears a blur region previously applied with `apply_blur_region()`.
+pub fn clear_blur_region(hwnd: HWND) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
+	let blur_behind = DWM_BLURBEHIND {
+		dwFlags: DWM_BB_ENABLE,
+		fEnable: 0,
+		hRgnBlur: 0,
+		fTransitionOnMaximized: 0
+	};
+	unsafe {
+		DwmEnableBlurBehindWindow(hwnd, &blur_behind);
+	}
+	Ok(())
+}
+
 pub fn apply_acrylic(hwnd: HWND, unified: bool, acrylic_blurbehind: bool, colour: Option<[u8; 4]>) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
 	if !unified && is_win11_22h2() {
 		unsafe {
 			extend_client_area(hwnd);
@@ -218,6 +421,10 @@ pub fn apply_acrylic(hwnd: HWND, unified: bool, acrylic_blurbehind: bool, colour
 }
 
 pub fn clear_acrylic(hwnd: HWND, unified: bool) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
 	if !unified && is_win11_22h2() {
 		unsafe {
 			reset_client_area(hwnd);
@@ -234,6 +441,10 @@ pub fn clear_acrylic(hwnd: HWND, unified: bool) -> Result<(), VibeError> {
 }
 
 pub fn apply_mica(hwnd: HWND) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
 	if is_win11_22h2() {
 		unsafe {
 			extend_client_area(hwnd);
@@ -251,6 +462,10 @@ pub fn apply_mica(hwnd: HWND) -> Result<(), VibeError> {
 }
 
 pub fn clear_mica(hwnd: HWND) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
 	if is_win11_22h2() {
 		unsafe {
 			reset_client_area(hwnd);
@@ -266,3 +481,88 @@ pub fn clear_mica(hwnd: HWND) -> Result<(), VibeError> {
 	}
 	Ok(())
 }
+
+/// The darker, tabbed-browser variant of Mica shipped in Windows 11 22H2.
+pub fn apply_tabbed_mica(hwnd: HWND) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
+	if is_win11_22h2() {
+		unsafe {
+			extend_client_area(hwnd);
+			DwmSetWindowAttribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &DWM_SYSTEMBACKDROP_TYPE::DWMSBT_TABBEDWINDOW as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"apply_tabbed_mica()\" is only available on Windows 11 22H2+"));
+	}
+	Ok(())
+}
+
+pub fn clear_tabbed_mica(hwnd: HWND) -> Result<(), VibeError> {
+	if !is_composition_enabled() {
+		return Err(VibeError::CompositionDisabled);
+	}
+
+	if is_win11_22h2() {
+		unsafe {
+			reset_client_area(hwnd);
+			DwmSetWindowAttribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &DWM_SYSTEMBACKDROP_TYPE::DWMSBT_DISABLE as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"clear_tabbed_mica()\" is only available on Windows 11 22H2+"));
+	}
+	Ok(())
+}
+
+pub fn set_corner_preference(hwnd: HWND, preference: DWM_WINDOW_CORNER_PREFERENCE) -> Result<(), VibeError> {
+	if is_win11() {
+		unsafe {
+			DwmSetWindowAttribute(hwnd, DWMWA_WINDOW_CORNER_PREFERENCE, &preference as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"set_corner_preference()\" is only available on Windows 11"));
+	}
+	Ok(())
+}
+
+/// Sets the window border colour, or hides the border entirely when `colour` is `None`.
+pub fn set_border_color(hwnd: HWND, colour: Option<[u8; 4]>) -> Result<(), VibeError> {
+	if is_win11() {
+		unsafe {
+			let colorref = pack_colorref(colour, DWMWA_COLOR_NONE);
+			DwmSetWindowAttribute(hwnd, DWMWA_BORDER_COLOR, &colorref as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"set_border_color()\" is only available on Windows 11"));
+	}
+	Ok(())
+}
+
+/// Sets the window caption (title bar) background colour, or resets it to the system default when
+/// `colour` is `None`.
+pub fn set_caption_color(hwnd: HWND, colour: Option<[u8; 4]>) -> Result<(), VibeError> {
+	if is_win11() {
+		unsafe {
+			let colorref = pack_colorref(colour, DWMWA_COLOR_DEFAULT);
+			DwmSetWindowAttribute(hwnd, DWMWA_CAPTION_COLOR, &colorref as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"set_caption_color()\" is only available on Windows 11"));
+	}
+	Ok(())
+}
+
+/// Sets the window caption title text colour, or resets it to the system default when `colour` is
+/// `None`.
+pub fn set_title_text_color(hwnd: HWND, colour: Option<[u8; 4]>) -> Result<(), VibeError> {
+	if is_win11() {
+		unsafe {
+			let colorref = pack_colorref(colour, DWMWA_COLOR_DEFAULT);
+			DwmSetWindowAttribute(hwnd, DWMWA_TEXT_COLOR, &colorref as *const _ as _, 4);
+		}
+	} else {
+		return Err(VibeError::UnsupportedPlatform("\"set_title_text_color()\" is only available on Windows 11"));
+	}
+	Ok(())
+}
@@ -29,6 +29,8 @@ pub enum VibeState {
 	Blurbehind,
 	#[cfg(target_os = "windows")]
 	Mica,
+	#[cfg(target_os = "windows")]
+	TabbedMica,
 }
 
 static VIBE_STATE: Lazy<RwLock<VibeState>> = Lazy::new(|| RwLock::new(VibeState::Uninitialized));
@@ -37,6 +39,8 @@ pub enum VibeError {
 	UnsupportedPlatform(&'static str),
 	UnknownEffect(String),
 	UnknownTheme(String),
+	UnknownCornerPreference(String),
+	CompositionDisabled,
 	Uninitialized,
 }
 
@@ -44,8 +48,12 @@ impl ToString for VibeError {
 	fn to_string(&self) -> String {
 		match self {
 			Self::UnsupportedPlatform(msg) => format!("Unsupported platform: {}", msg),
-			Self::UnknownEffect(effect) => format!("Expected `effect` to be one of ['mica', 'acrylic', 'unified-acrylic', 'blurbehind']; got `{}`", effect),
-			Self::UnknownTheme(theme) => format!("Expected `theme` to be one of ['dark', 'light']; got `{}`", theme),
+			Self::UnknownEffect(effect) => format!("Expected `effect` to be one of ['mica', 'tabbed', 'acrylic', 'unified-acrylic', 'blurbehind']; got `{}`", effect),
+			Self::UnknownTheme(theme) => format!("Expected `theme` to be one of ['dark', 'light', 'auto']; got `{}`", theme),
+			Self::UnknownCornerPreference(preference) => {
+				format!("Expected `preference` to be one of ['default', 'round', 'round-small', 'square']; got `{}`", preference)
+			}
+			Self::CompositionDisabled => "Desktop Window Manager composition is disabled; blur/acrylic/mica effects are unavailable".to_owned(),
 			Self::Uninitialized => "`vibe` was not setup before calling `applyEffect`!".to_owned(),
 		}
 	}
@@ -53,6 +61,8 @@ impl ToString for VibeError {
 
 #[cfg(target_os = "windows")]
 pub mod dwm_win32;
+#[cfg(target_os = "windows")]
+pub mod watcher_win32;
 
 #[cfg(target_os = "windows")]
 fn get_native_window_handle(cx: &mut FunctionContext) -> NeonResult<windows_sys::Win32::Foundation::HWND> {
@@ -114,6 +124,10 @@ pub fn apply_effect(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 				let _ = dwm_win32::clear_mica(handle);
 			}
 			#[cfg(target_os = "windows")]
+			VibeState::TabbedMica => {
+				let _ = dwm_win32::clear_tabbed_mica(handle);
+			}
+			#[cfg(target_os = "windows")]
 			VibeState::UnifiedAcrylic | VibeState::Blurbehind => {
 				let _ = dwm_win32::clear_acrylic(handle, true);
 			}
@@ -192,6 +206,14 @@ pub fn apply_effect(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 			}
 			Err(e) => cx.throw_error(e.to_string())?,
 		},
+		#[cfg(target_os = "windows")]
+		"tabbed" => match dwm_win32::apply_tabbed_mica(handle) {
+			Ok(_) => {
+				*state = VibeState::TabbedMica;
+				Ok(cx.undefined())
+			}
+			Err(e) => cx.throw_error(e.to_string())?,
+		},
 		_ => cx.throw_type_error(VibeError::UnknownEffect(effect).to_string()),
 	}
 }
@@ -209,6 +231,10 @@ pub fn clear_effects(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 				let _ = dwm_win32::clear_mica(handle);
 			}
 			#[cfg(target_os = "windows")]
+			VibeState::TabbedMica => {
+				let _ = dwm_win32::clear_tabbed_mica(handle);
+			}
+			#[cfg(target_os = "windows")]
 			VibeState::UnifiedAcrylic | VibeState::Blurbehind => {
 				let _ = dwm_win32::clear_acrylic(handle, true);
 			}
@@ -232,18 +258,177 @@ pub fn force_theme(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 	match effect.as_str() {
 		"dark" => {
 			#[cfg(target_os = "windows")]
-			let _ = dwm_win32::force_dark_theme(handle);
+			{
+				// switching to an explicit theme always wins over a prior "auto" subscription
+				dwm_win32::unwatch_system_theme(handle);
+				let _ = dwm_win32::force_dark_theme(handle);
+			}
 			Ok(cx.undefined())
 		}
 		"light" => {
 			#[cfg(target_os = "windows")]
-			let _ = dwm_win32::force_light_theme(handle);
+			{
+				dwm_win32::unwatch_system_theme(handle);
+				let _ = dwm_win32::force_light_theme(handle);
+			}
+			Ok(cx.undefined())
+		}
+		"auto" => {
+			#[cfg(target_os = "windows")]
+			{
+				let theme = dwm_win32::detect_system_theme();
+				let _ = match theme {
+					dwm_win32::Theme::Dark => dwm_win32::force_dark_theme(handle),
+					dwm_win32::Theme::Light => dwm_win32::force_light_theme(handle)
+				};
+				let _ = dwm_win32::watch_system_theme(handle);
+			}
 			Ok(cx.undefined())
 		}
 		_ => cx.throw_type_error(VibeError::UnknownTheme(effect).to_string()),
 	}
 }
 
+#[cfg(target_os = "windows")]
+pub fn set_corner_preference(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	let preference = cx.argument::<JsString>(1)?.value(&mut cx);
+
+	let preference = match preference.as_str() {
+		"default" => dwm_win32::DWM_WINDOW_CORNER_PREFERENCE::DWMWCP_DEFAULT,
+		"square" => dwm_win32::DWM_WINDOW_CORNER_PREFERENCE::DWMWCP_DONOTROUND,
+		"round" => dwm_win32::DWM_WINDOW_CORNER_PREFERENCE::DWMWCP_ROUND,
+		"round-small" => dwm_win32::DWM_WINDOW_CORNER_PREFERENCE::DWMWCP_ROUNDSMALL,
+		_ => return cx.throw_type_error(VibeError::UnknownCornerPreference(preference).to_string())
+	};
+
+	match dwm_win32::set_corner_preference(handle, preference) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+fn parse_optional_colour(cx: &mut FunctionContext, colour: Option<Handle<JsValue>>) -> NeonResult<Option<[u8; 4]>> {
+	Ok(match colour {
+		Some(t) => match csscolorparser::parse(&t.downcast_or_throw::<JsString, FunctionContext>(cx)?.value(cx)) {
+			Ok(colour) => Some(colour.to_rgba8()),
+			Err(_) => None
+		},
+		None => None
+	})
+}
+
+#[cfg(target_os = "windows")]
+pub fn set_border_color(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	let colour = cx.argument_opt(1);
+	let colour = parse_optional_colour(&mut cx, colour)?;
+
+	match dwm_win32::set_border_color(handle, colour) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn set_caption_color(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	let colour = cx.argument_opt(1);
+	let colour = parse_optional_colour(&mut cx, colour)?;
+
+	match dwm_win32::set_caption_color(handle, colour) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn set_title_text_color(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	let colour = cx.argument_opt(1);
+	let colour = parse_optional_colour(&mut cx, colour)?;
+
+	match dwm_win32::set_title_text_color(handle, colour) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn apply_blur_region(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	let rect = cx.argument_opt(1);
+	let transition_on_maximized = cx
+		.argument_opt(2)
+		.map(|t| t.downcast_or_throw::<JsBoolean, FunctionContext>(&mut cx))
+		.transpose()?
+		.map(|b| b.value(&mut cx))
+		.unwrap_or(false);
+
+	let region = match rect {
+		Some(t) if !t.is_a::<JsUndefined, _>(&mut cx) && !t.is_a::<JsNull, _>(&mut cx) => {
+			let rect = t.downcast_or_throw::<JsObject, FunctionContext>(&mut cx)?;
+			let x: Handle<JsNumber> = rect.get(&mut cx, "x")?;
+			let y: Handle<JsNumber> = rect.get(&mut cx, "y")?;
+			let width: Handle<JsNumber> = rect.get(&mut cx, "width")?;
+			let height: Handle<JsNumber> = rect.get(&mut cx, "height")?;
+			let x = x.value(&mut cx) as i32;
+			let y = y.value(&mut cx) as i32;
+			let width = width.value(&mut cx) as i32;
+			let height = height.value(&mut cx) as i32;
+			Some(unsafe { windows_sys::Win32::Graphics::Gdi::CreateRectRgn(x, y, x + width, y + height) })
+		}
+		_ => None
+	};
+
+	match dwm_win32::apply_blur_region(handle, region, transition_on_maximized) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn clear_blur_region(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let handle = get_native_window_handle(&mut cx)?;
+	match dwm_win32::clear_blur_region(handle) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn start_watching(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	let effect = cx.argument::<JsString>(0)?.value(&mut cx);
+	if !matches!(effect.as_str(), "mica" | "tabbed" | "acrylic" | "unified-acrylic" | "blurbehind") {
+		return cx.throw_type_error(VibeError::UnknownEffect(effect).to_string());
+	}
+	let colour = cx.argument_opt(1);
+	let colour = parse_optional_colour(&mut cx, colour)?;
+	let theme = match cx.argument_opt(2) {
+		Some(t) => match t.downcast::<JsString, _>(&mut cx) {
+			Ok(s) => match s.value(&mut cx).as_str() {
+				"dark" => Some(dwm_win32::Theme::Dark),
+				"light" => Some(dwm_win32::Theme::Light),
+				_ => None
+			},
+			Err(_) => None
+		},
+		None => None
+	};
+
+	match watcher_win32::start_watching(watcher_win32::WatchRule { effect, colour, theme }) {
+		Ok(_) => Ok(cx.undefined()),
+		Err(e) => cx.throw_error(e.to_string())?
+	}
+}
+
+#[cfg(target_os = "windows")]
+pub fn stop_watching(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+	watcher_win32::stop_watching();
+	Ok(cx.undefined())
+}
+
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
 	let platform = cx.empty_object();
@@ -264,5 +449,18 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
 	cx.export_function("clearEffects", clear_effects)?;
 	cx.export_function("forceTheme", force_theme)?;
 	cx.export_function("setup", setup)?;
+
+	#[cfg(target_os = "windows")]
+	{
+		cx.export_function("setCornerPreference", set_corner_preference)?;
+		cx.export_function("setBorderColor", set_border_color)?;
+		cx.export_function("setCaptionColor", set_caption_color)?;
+		cx.export_function("setTitleTextColor", set_title_text_color)?;
+		cx.export_function("applyBlurRegion", apply_blur_region)?;
+		cx.export_function("clearBlurRegion", clear_blur_region)?;
+		cx.export_function("startWatching", start_watching)?;
+		cx.export_function("stopWatching", stop_watching)?;
+	}
+
 	Ok(())
 }
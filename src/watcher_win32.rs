@@ -0,0 +1,134 @@
+// Copyright (c) 2022 pyke.io (https://github.com/pykeio/vibe)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(non_snake_case, clippy::upper_case_acronyms, non_camel_case_types)]
+
+use std::{collections::HashSet, sync::RwLock};
+
+use once_cell::sync::Lazy;
+use windows_sys::Win32::{
+	Foundation::HWND,
+	UI::{
+		Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK},
+		WindowsAndMessaging::{
+			GetAncestor, GetWindowLongPtrW, IsWindowVisible, EVENT_OBJECT_SHOW, EVENT_SYSTEM_FOREGROUND, GA_ROOT, GWL_EXSTYLE, WINEVENT_OUTOFCONTEXT, WS_EX_TOOLWINDOW
+		}
+	}
+};
+
+use crate::{dwm_win32, dwm_win32::Theme, VibeError};
+
+/// A rule describing what the watcher should apply to every newly shown top-level window.
+#[derive(Clone)]
+pub struct WatchRule {
+	pub effect: String,
+	pub colour: Option<[u8; 4]>,
+	pub theme: Option<Theme>
+}
+
+static WATCH_RULE: Lazy<RwLock<Option<WatchRule>>> = Lazy::new(|| RwLock::new(None));
+// `EVENT_OBJECT_SHOW` and `EVENT_SYSTEM_FOREGROUND` aren't contiguous IDs, so a single
+// `SetWinEventHook` range can't cover both without also matching everything in between; each gets
+// its own hook instead.
+static WATCH_HOOKS: Lazy<RwLock<Option<(HWINEVENTHOOK, HWINEVENTHOOK)>>> = Lazy::new(|| RwLock::new(None));
+static SEEN_WINDOWS: Lazy<RwLock<HashSet<HWND>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+fn apply_rule(hwnd: HWND, rule: &WatchRule) {
+	let _ = match rule.effect.as_str() {
+		"mica" => dwm_win32::apply_mica(hwnd),
+		"tabbed" => dwm_win32::apply_tabbed_mica(hwnd),
+		"acrylic" => dwm_win32::apply_acrylic(hwnd, false, true, rule.colour),
+		"unified-acrylic" => dwm_win32::apply_acrylic(hwnd, true, true, rule.colour),
+		"blurbehind" => dwm_win32::apply_acrylic(hwnd, true, false, rule.colour),
+		_ => Ok(())
+	};
+
+	if let Some(theme) = rule.theme {
+		let _ = match theme {
+			Theme::Dark => dwm_win32::force_dark_theme(hwnd),
+			Theme::Light => dwm_win32::force_light_theme(hwnd)
+		};
+	}
+}
+
+/// Filters the event hook down to visible top-level windows (their own `GA_ROOT`) without the
+/// `WS_EX_TOOLWINDOW` style. Child windows and tool windows are skipped; owned popups that lack
+/// the tool-window style are still matched.
+unsafe fn is_top_level_window(hwnd: HWND) -> bool {
+	if GetAncestor(hwnd, GA_ROOT) != hwnd {
+		return false;
+	}
+	if IsWindowVisible(hwnd) == 0 {
+		return false;
+	}
+	GetWindowLongPtrW(hwnd, GWL_EXSTYLE) & (WS_EX_TOOLWINDOW as isize) == 0
+}
+
+unsafe extern "system" fn win_event_proc(_hook: HWINEVENTHOOK, _event: u32, hwnd: HWND, id_object: i32, id_child: i32, _thread: u32, _time: u32) {
+	// OBJID_WINDOW / CHILDID_SELF
+	if id_object != 0 || id_child != 0 || hwnd == 0 || !is_top_level_window(hwnd) {
+		return;
+	}
+
+	{
+		let mut seen = SEEN_WINDOWS.write().unwrap();
+		if !seen.insert(hwnd) {
+			return;
+		}
+	}
+
+	if let Some(rule) = WATCH_RULE.read().unwrap().as_ref() {
+		apply_rule(hwnd, rule);
+	}
+}
+
+/// Starts applying `rule` to every top-level window as it appears, instead of requiring one
+/// explicit `apply_effect`/`force_theme` call per `HWND`.
+pub fn start_watching(rule: WatchRule) -> Result<(), VibeError> {
+	*WATCH_RULE.write().unwrap() = Some(rule);
+
+	let mut hooks = WATCH_HOOKS.write().unwrap();
+	if hooks.is_some() {
+		// already watching; just swap the rule above
+		return Ok(());
+	}
+
+	let show_hook = unsafe { SetWinEventHook(EVENT_OBJECT_SHOW, EVENT_OBJECT_SHOW, 0, Some(win_event_proc), 0, 0, WINEVENT_OUTOFCONTEXT) };
+	if show_hook == 0 {
+		return Err(VibeError::UnsupportedPlatform("\"start_watching()\" failed to install the window event hook"));
+	}
+
+	let foreground_hook = unsafe { SetWinEventHook(EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_FOREGROUND, 0, Some(win_event_proc), 0, 0, WINEVENT_OUTOFCONTEXT) };
+	if foreground_hook == 0 {
+		unsafe {
+			UnhookWinEvent(show_hook);
+		}
+		return Err(VibeError::UnsupportedPlatform("\"start_watching()\" failed to install the window event hook"));
+	}
+
+	*hooks = Some((show_hook, foreground_hook));
+	Ok(())
+}
+
+/// Stops the watcher and forgets every window it has already processed.
+pub fn stop_watching() {
+	if let Some((show_hook, foreground_hook)) = WATCH_HOOKS.write().unwrap().take() {
+		unsafe {
+			UnhookWinEvent(show_hook);
+			UnhookWinEvent(foreground_hook);
+		}
+	}
+	SEEN_WINDOWS.write().unwrap().clear();
+	*WATCH_RULE.write().unwrap() = None;
+}